@@ -0,0 +1,8 @@
+use crate::ops::Op;
+
+/// A user-defined (`:` ... `;`) word, already compiled to opcodes.
+#[derive(Debug, Clone)]
+pub struct WordElement {
+	pub name: String,
+	pub body: Vec<Op>,
+}
@@ -0,0 +1,97 @@
+pub mod simple;
+pub mod complex;
+
+use pest::iterators::Pair;
+
+use crate::parser::{Parse, Rule};
+
+use simple::literal::Literal;
+
+/// One element of a parsed line or colon-definition body.
+///
+/// Unlike a flat token stream, the control-flow variants hold their branches
+/// as nested `Vec<Token>`s so that matching `IF`/`THEN`, `BEGIN`/`UNTIL` and
+/// `DO`/`LOOP` pairs are resolved once, at parse time; [`crate::ForthInterpreter::compile_tokens`]
+/// later lowers this tree into a flat, jump-addressed [`crate::ops::Op`] vector.
+#[derive(Debug, Clone)]
+pub enum Token {
+	Literal(Literal),
+	Call(String),
+	Definition { name: String, body: Vec<Token> },
+	If {
+		true_branch: Vec<Token>,
+		false_branch: Vec<Token>,
+	},
+	BeginUntil {
+		body: Vec<Token>,
+	},
+	BeginWhileRepeat {
+		condition: Vec<Token>,
+		body: Vec<Token>,
+	},
+	DoLoop {
+		body: Vec<Token>,
+	},
+}
+
+fn parse_branch(pair: Pair<Rule>) -> Vec<Token> {
+	pair.into_inner().map(Token::parse).collect()
+}
+
+impl Parse for Token {
+	fn parse(pair: Pair<Rule>) -> Self {
+		match pair.as_rule() {
+			Rule::word_element => Token::parse(pair.into_inner().next().unwrap()),
+			Rule::literal => Token::Literal(Literal::parse(pair)),
+			Rule::word_call => Token::Call(pair.as_str().to_string()),
+			Rule::definition => {
+				let mut inner = pair.into_inner();
+				let name = inner.next().unwrap().as_str().to_string();
+				let body = inner.map(Token::parse).collect();
+				Token::Definition { name, body }
+			}
+			Rule::if_then => {
+				let mut branches = pair.into_inner();
+				let true_branch = parse_branch(branches.next().unwrap());
+				let false_branch = branches.next().map(parse_branch).unwrap_or_default();
+				Token::If { true_branch, false_branch }
+			}
+			Rule::begin_until => Token::BeginUntil {
+				body: parse_branch(pair.into_inner().next().unwrap()),
+			},
+			Rule::begin_while_repeat => {
+				let mut branches = pair.into_inner();
+				let condition = parse_branch(branches.next().unwrap());
+				let body = parse_branch(branches.next().unwrap());
+				Token::BeginWhileRepeat { condition, body }
+			}
+			Rule::do_loop => Token::DoLoop {
+				body: parse_branch(pair.into_inner().next().unwrap()),
+			},
+			_ => unreachable!("unexpected rule in word_element: {:?}", pair.as_rule()),
+		}
+	}
+}
+
+/// A single parsed line of input, not yet compiled to opcodes.
+#[derive(Debug, Clone, Default)]
+pub struct Line {
+	tokens: Vec<Token>,
+}
+
+impl Line {
+	pub(crate) fn into_tokens(self) -> Vec<Token> {
+		self.tokens
+	}
+}
+
+impl Parse for Line {
+	fn parse(pair: Pair<Rule>) -> Self {
+		let tokens = pair
+			.into_inner()
+			.filter(|p| p.as_rule() != Rule::EOI)
+			.map(Token::parse)
+			.collect();
+		Self { tokens }
+	}
+}
@@ -2,10 +2,10 @@ use cpython::{PyString, Python, ToPyObject};
 use std::{
     cmp::Ordering,
     fmt::{self, Display},
+    hash::{Hash, Hasher},
 };
 
 use crate::parser::*;
-use crate::{ExecuteExt, Result};
 
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Pointer {
@@ -21,14 +21,16 @@ impl Pointer {
 
 type PointerType = Pointer;
 type IntegerType = i64;
+type FloatType = f64;
 type StringType = std::string::String;
 
 type ArrayType = Vec<Literal>;
 
-#[derive(Debug, Clone, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub enum Literal {
     Pointer(PointerType),
     Integer(IntegerType),
+    Float(FloatType),
     String(StringType),
 
     Array(ArrayType),
@@ -36,19 +38,16 @@ pub enum Literal {
     Unknown,
 }
 
-impl ExecuteExt for Literal {
-    fn execute(&mut self, interpreter: &mut crate::ForthInterpreter) -> Result<()> {
-        interpreter.stack.push(self.clone());
-        Ok(())
-    }
-}
-
 impl Parse for Literal {
     fn parse(pair: pest::iterators::Pair<Rule>) -> Self {
         let inner = pair.into_inner().next().unwrap();
         match inner.as_rule() {
+            Rule::float => Literal::Float(inner.as_str().parse::<f64>().unwrap()),
             Rule::integer => Literal::Integer(inner.as_str().parse::<i64>().unwrap()),
-            Rule::string => Literal::String(inner.as_str().to_string()),
+            Rule::string => {
+                let quoted = inner.as_str();
+                Literal::String(quoted[1..quoted.len() - 1].to_string())
+            }
             _ => unreachable!(),
         }
     }
@@ -60,6 +59,9 @@ impl Display for Literal {
             Literal::Integer(i) => {
                 write!(f, "{}", i)
             }
+            Literal::Float(x) => {
+                write!(f, "{}", x)
+            }
             Literal::String(s) => {
                 write!(f, "{}", s)
             }
@@ -86,6 +88,13 @@ impl PartialEq for Literal {
                     false
                 }
             }
+            Literal::Float(x) => {
+                if let Literal::Float(y) = *other {
+                    *x == y
+                } else {
+                    false
+                }
+            }
             Literal::Pointer(i) => {
                 if let Literal::Pointer(j) = other {
                     i == j
@@ -114,6 +123,25 @@ impl PartialEq for Literal {
     }
 }
 
+// `Literal::Float` makes this relation non-reflexive for NaN, same as `f64`
+// itself; the type is still marked `Eq` so it can be used where an `Eq`
+// bound is required, but for NaN floats it does not uphold reflexivity.
+impl Eq for Literal {}
+
+impl Hash for Literal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Literal::Integer(i) => i.hash(state),
+            Literal::Float(x) => x.to_bits().hash(state),
+            Literal::Pointer(p) => p.hash(state),
+            Literal::String(s) => s.hash(state),
+            Literal::Array(arr) => arr.hash(state),
+            Literal::Unknown => {}
+        }
+    }
+}
+
 impl PartialOrd for Literal {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self {
@@ -124,6 +152,13 @@ impl PartialOrd for Literal {
                     None
                 }
             }
+            Literal::Float(x) => {
+                if let Literal::Float(y) = *other {
+                    Some(x.total_cmp(&y))
+                } else {
+                    None
+                }
+            }
             Literal::Pointer(i) => {
                 if let Literal::Pointer(j) = other {
                     i.partial_cmp(&j)
@@ -162,6 +197,12 @@ impl From<i64> for Literal {
     }
 }
 
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::Float(value)
+    }
+}
+
 impl From<&str> for Literal {
     fn from(value: &str) -> Self {
         Literal::String(value.into())
@@ -179,6 +220,7 @@ impl ToPyObject for Literal {
     fn to_py_object(&self, py: Python) -> Self::ObjectType {
         match self {
             Literal::Integer(i) => PyString::new(py, &i.to_string()),
+            Literal::Float(x) => PyString::new(py, &x.to_string()),
             Literal::String(i) => PyString::new(py, i.as_str()),
             _ => unreachable!(),
         }
@@ -0,0 +1,24 @@
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForthError {
+	StackUnderflow,
+	InvalidOperands,
+	UnknownWord(String),
+	UnbalancedControlFlow,
+	CorruptImage(String),
+}
+
+impl Display for ForthError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ForthError::StackUnderflow => write!(f, "stack underflow"),
+			ForthError::InvalidOperands => write!(f, "invalid operands"),
+			ForthError::UnknownWord(name) => write!(f, "unknown word: {}", name),
+			ForthError::UnbalancedControlFlow => write!(f, "unbalanced control-flow structure"),
+			ForthError::CorruptImage(reason) => write!(f, "corrupt image: {}", reason),
+		}
+	}
+}
+
+impl std::error::Error for ForthError {}
@@ -0,0 +1,285 @@
+//! Binary save/restore of interpreter state: the stack, `variables`,
+//! `constants`, and `user_words`, so a REPL session can be snapshotted and
+//! resumed, or shared between machines.
+//!
+//! The format is a self-describing tag-length-value encoding: a fixed
+//! header, then one leading byte per [`Literal`] identifying its variant,
+//! followed by its payload: fixed-width little-endian for integers, floats
+//! and pointers, length-prefixed for strings, count-prefixed for arrays,
+//! and empty for `Unknown`. Native words are rebuilt by
+//! [`ForthInterpreter::new`] rather than serialized, since they're fixed
+//! per build.
+
+use std::io::{Read, Write};
+
+use crate::entities::complex::definition::WordElement;
+use crate::entities::simple::literal::{Literal, Pointer};
+use crate::errors::ForthError::CorruptImage;
+use crate::ops::Op;
+use crate::{ForthInterpreter, Result, Variable};
+
+const MAGIC: &[u8; 4] = b"FTHI";
+const VERSION: u8 = 1;
+
+fn write_u8<W: Write>(writer: &mut W, value: u8) -> Result<()> {
+	writer.write_all(&[value]).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> Result<()> {
+	writer.write_all(&value.to_le_bytes()).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_u64<W: Write>(writer: &mut W, value: u64) -> Result<()> {
+	writer.write_all(&value.to_le_bytes()).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_i64<W: Write>(writer: &mut W, value: i64) -> Result<()> {
+	writer.write_all(&value.to_le_bytes()).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_f64<W: Write>(writer: &mut W, value: f64) -> Result<()> {
+	writer.write_all(&value.to_bits().to_le_bytes()).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<()> {
+	write_u32(writer, bytes.len() as u32)?;
+	writer.write_all(bytes).map_err(|e| CorruptImage(e.to_string()))
+}
+
+fn write_string<W: Write>(writer: &mut W, value: &str) -> Result<()> {
+	write_bytes(writer, value.as_bytes())
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> Result<u8> {
+	let mut buf = [0u8; 1];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(buf[0])
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+	let mut buf = [0u8; 4];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+	let mut buf = [0u8; 8];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(u64::from_le_bytes(buf))
+}
+
+fn read_i64<R: Read>(reader: &mut R) -> Result<i64> {
+	let mut buf = [0u8; 8];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(i64::from_le_bytes(buf))
+}
+
+fn read_f64<R: Read>(reader: &mut R) -> Result<f64> {
+	let mut buf = [0u8; 8];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(f64::from_bits(u64::from_le_bytes(buf)))
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+	let len = read_u32(reader)? as usize;
+	let mut buf = vec![0u8; len];
+	reader.read_exact(&mut buf).map_err(|_| CorruptImage("truncated image".to_string()))?;
+	Ok(buf)
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String> {
+	let bytes = read_bytes(reader)?;
+	String::from_utf8(bytes).map_err(|_| CorruptImage("invalid utf-8 in image".to_string()))
+}
+
+fn write_literal<W: Write>(writer: &mut W, literal: &Literal) -> Result<()> {
+	match literal {
+		Literal::Pointer(p) => {
+			write_u8(writer, 0)?;
+			write_u64(writer, p.address as u64)?;
+			write_u64(writer, p.offset as u64)?;
+		}
+		Literal::Integer(i) => {
+			write_u8(writer, 1)?;
+			write_i64(writer, *i)?;
+		}
+		Literal::Float(x) => {
+			write_u8(writer, 2)?;
+			write_f64(writer, *x)?;
+		}
+		Literal::String(s) => {
+			write_u8(writer, 3)?;
+			write_string(writer, s)?;
+		}
+		Literal::Array(items) => {
+			write_u8(writer, 4)?;
+			write_u32(writer, items.len() as u32)?;
+			for item in items {
+				write_literal(writer, item)?;
+			}
+		}
+		Literal::Unknown => write_u8(writer, 5)?,
+	}
+	Ok(())
+}
+
+fn read_literal<R: Read>(reader: &mut R) -> Result<Literal> {
+	match read_u8(reader)? {
+		0 => {
+			let address = read_u64(reader)? as usize;
+			let offset = read_u64(reader)? as usize;
+			Ok(Literal::Pointer(Pointer::new(address, offset)))
+		}
+		1 => Ok(Literal::Integer(read_i64(reader)?)),
+		2 => Ok(Literal::Float(read_f64(reader)?)),
+		3 => Ok(Literal::String(read_string(reader)?)),
+		4 => {
+			let count = read_u32(reader)? as usize;
+			let mut items = Vec::with_capacity(count);
+			for _ in 0..count {
+				items.push(read_literal(reader)?);
+			}
+			Ok(Literal::Array(items))
+		}
+		5 => Ok(Literal::Unknown),
+		_ => Err(CorruptImage("unknown literal tag".to_string())),
+	}
+}
+
+fn write_op<W: Write>(writer: &mut W, op: &Op) -> Result<()> {
+	match op {
+		Op::PushLiteral(literal) => {
+			write_u8(writer, 0)?;
+			write_literal(writer, literal)?;
+		}
+		Op::CallNative(index) => {
+			write_u8(writer, 1)?;
+			write_u32(writer, *index as u32)?;
+		}
+		Op::CallUser(index) => {
+			write_u8(writer, 2)?;
+			write_u32(writer, *index as u32)?;
+		}
+		Op::BranchFalse(target) => {
+			write_u8(writer, 3)?;
+			write_u32(writer, *target as u32)?;
+		}
+		Op::Jump(target) => {
+			write_u8(writer, 4)?;
+			write_u32(writer, *target as u32)?;
+		}
+		Op::DoSetup(end) => {
+			write_u8(writer, 5)?;
+			write_u32(writer, *end as u32)?;
+		}
+		Op::DoNext(start) => {
+			write_u8(writer, 6)?;
+			write_u32(writer, *start as u32)?;
+		}
+	}
+	Ok(())
+}
+
+fn read_op<R: Read>(reader: &mut R) -> Result<Op> {
+	match read_u8(reader)? {
+		0 => Ok(Op::PushLiteral(read_literal(reader)?)),
+		1 => Ok(Op::CallNative(read_u32(reader)? as usize)),
+		2 => Ok(Op::CallUser(read_u32(reader)? as usize)),
+		3 => Ok(Op::BranchFalse(read_u32(reader)? as usize)),
+		4 => Ok(Op::Jump(read_u32(reader)? as usize)),
+		5 => Ok(Op::DoSetup(read_u32(reader)? as usize)),
+		6 => Ok(Op::DoNext(read_u32(reader)? as usize)),
+		_ => Err(CorruptImage("unknown opcode tag".to_string())),
+	}
+}
+
+impl ForthInterpreter {
+	pub fn save_image<W: Write>(&self, writer: &mut W) -> Result<()> {
+		writer.write_all(MAGIC).map_err(|e| CorruptImage(e.to_string()))?;
+		write_u8(writer, VERSION)?;
+
+		write_u32(writer, self.stack.length() as u32)?;
+		for literal in self.stack.iter() {
+			write_literal(writer, literal)?;
+		}
+
+		write_u32(writer, self.variables.len() as u32)?;
+		for variable in &self.variables {
+			write_string(writer, &variable.name)?;
+			match &variable.value {
+				Some(value) => {
+					write_u8(writer, 1)?;
+					write_literal(writer, value)?;
+				}
+				None => write_u8(writer, 0)?,
+			}
+		}
+
+		write_u32(writer, self.constants.len() as u32)?;
+		for (name, value) in &self.constants {
+			write_string(writer, name)?;
+			write_literal(writer, value)?;
+		}
+
+		write_u32(writer, self.user_words.len() as u32)?;
+		for word in &self.user_words {
+			write_string(writer, &word.name)?;
+			write_u32(writer, word.body.len() as u32)?;
+			for op in &word.body {
+				write_op(writer, op)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	pub fn load_image<R: Read>(reader: &mut R) -> Result<Self> {
+		let mut magic = [0u8; 4];
+		reader.read_exact(&mut magic).map_err(|_| CorruptImage("truncated image".to_string()))?;
+		if &magic != MAGIC {
+			return Err(CorruptImage("not a Forth image".to_string()));
+		}
+		if read_u8(reader)? != VERSION {
+			return Err(CorruptImage("unsupported image version".to_string()));
+		}
+
+		let mut interpreter = Self::new();
+
+		let stack_len = read_u32(reader)? as usize;
+		for _ in 0..stack_len {
+			interpreter.stack.push(read_literal(reader)?);
+		}
+
+		let variables_len = read_u32(reader)? as usize;
+		for _ in 0..variables_len {
+			let name = read_string(reader)?;
+			let value = match read_u8(reader)? {
+				0 => None,
+				1 => Some(read_literal(reader)?),
+				_ => return Err(CorruptImage("invalid variable tag".to_string())),
+			};
+			interpreter.variables.push(Variable { name, value });
+		}
+
+		let constants_len = read_u32(reader)? as usize;
+		for _ in 0..constants_len {
+			let name = read_string(reader)?;
+			let value = read_literal(reader)?;
+			interpreter.constants.insert(name, value);
+		}
+
+		let user_words_len = read_u32(reader)? as usize;
+		for _ in 0..user_words_len {
+			let name = read_string(reader)?;
+			let body_len = read_u32(reader)? as usize;
+			let mut body = Vec::with_capacity(body_len);
+			for _ in 0..body_len {
+				body.push(read_op(reader)?);
+			}
+			interpreter.user_names.insert(name.clone(), interpreter.user_words.len());
+			interpreter.user_words.push(WordElement { name, body });
+		}
+
+		Ok(interpreter)
+	}
+}
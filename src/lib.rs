@@ -9,71 +9,94 @@ mod entities;
 
 mod stack;
 mod errors;
+mod ops;
+mod image;
 pub mod parser;
 pub mod words;
 
-use std::{collections::HashMap};
+use std::{collections::HashMap, fmt};
 
 use stack::Stack;
 use entities::{simple::literal::Literal, complex::definition::WordElement};
-use errors::ForthError::{self, StackUnderflow, InvalidOperands};
+use errors::ForthError::{self, StackUnderflow, InvalidOperands, UnbalancedControlFlow};
 
 use pest::Parser;
 use parser::*;
 
 type Result<T> = std::result::Result<T, ForthError>;
 
+#[macro_export]
 macro_rules! ternary {
     ($c:expr, $v:expr, $v1:expr) => {
         if $c {$v} else {$v1}
     };
 }
 
-type WordFn = fn(&mut ForthInterpreter) -> Result<()>;
-
-trait ExecuteExt {
-	fn execute(&mut self, interpreter: &mut ForthInterpreter) -> Result<()>;
-}
+pub(crate) type WordFn = fn(&mut ForthInterpreter) -> Result<()>;
 
 #[derive(Debug, Clone)]
-pub struct Variable { 
+pub struct Variable {
 	name: String,
 	value: Option<Literal>,
 }
 
+impl fmt::Display for Variable {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.value {
+			Some(value) => write!(f, "{}: {}", self.name, value),
+			None => write!(f, "{}: <unset>", self.name),
+		}
+	}
+}
+
 pub struct ForthInterpreter {
 	stack: Stack<Literal>,
 	
 	variables: Vec<Variable>,
 	constants: HashMap<String, Literal>, // No need in Option cause constant is initialized always
 
-	native_words: HashMap<String, WordFn>,
-	user_words: HashMap<String, WordElement>,
+	native_words: Vec<WordFn>,
+	native_names: HashMap<String, usize>,
+
+	user_words: Vec<WordElement>,
+	user_names: HashMap<String, usize>,
+
+	loop_stack: Vec<(i64, i64)>,
 }
 
 impl ForthInterpreter {
-	pub fn new() -> Self {		
+	pub fn new() -> Self {
+		let primitives: Vec<(&str, WordFn)> = vec![
+			("+", ForthInterpreter::add), ("-", ForthInterpreter::sub),
+			("*", ForthInterpreter::mul), ("/", ForthInterpreter::div),
+			("dup", ForthInterpreter::dup), ("drop", ForthInterpreter::drop),
+			("swap", ForthInterpreter::swap), ("over", ForthInterpreter::over),
+			("rot", ForthInterpreter::rot), (".", ForthInterpreter::print_top),
+			("emit", ForthInterpreter::emit), ("cr", ForthInterpreter::cr),
+			("=", ForthInterpreter::equal), ("<", ForthInterpreter::less_than),
+			(">", ForthInterpreter::greater_than), ("invert", ForthInterpreter::invert),
+			("and", ForthInterpreter::and), ("or", ForthInterpreter::or),
+			("!", ForthInterpreter::store_variable), ("@", ForthInterpreter::fetch_variable),
+			("i", ForthInterpreter::loop_index),
+		];
+		let native_words: Vec<(&str, WordFn)> = primitives.into_iter().chain(words::stdlib::table()).collect();
+		let native_names = native_words.iter().enumerate().map(|(i, (name, _))| (name.to_string(), i)).collect();
+
 		Self {
 			stack: Stack::new(),
 			variables: Vec::new(),
 			constants: HashMap::new(),
 
-			native_words: [
-				("+".into(), ForthInterpreter::add as WordFn), ("-".into(), ForthInterpreter::sub),
-				("*".into(), ForthInterpreter::mul), ("/".into(), ForthInterpreter::div),
-				("dup".into(), ForthInterpreter::dup), ("drop".into(), ForthInterpreter::drop),
-				("swap".into(), ForthInterpreter::swap), ("over".into(), ForthInterpreter::over),
-				("rot".into(), ForthInterpreter::rot), (".".into(), ForthInterpreter::print_top),
-				("emit".into(), ForthInterpreter::emit), ("cr".into(), ForthInterpreter::cr),
-				("=".into(), ForthInterpreter::equal), ("<".into(), ForthInterpreter::less_than),
-				(">".into(), ForthInterpreter::greater_than), ("invert".into(), ForthInterpreter::invert),
-				("and".into(), ForthInterpreter::and), ("or".into(), ForthInterpreter::or),
-				("!".into(), ForthInterpreter::store_variable), ("@".into(), ForthInterpreter::fetch_variable),
-			].iter().cloned().collect(),
-			user_words: HashMap::<String, WordElement>::new(),
+			native_words: native_words.into_iter().map(|(_, f)| f).collect(),
+			native_names,
+
+			user_words: Vec::new(),
+			user_names: HashMap::new(),
+
+			loop_stack: Vec::new(),
 		}
 	}
-	
+
 	fn get_unary_operand(&mut self) -> Result<Literal> {
 		Ok(self.stack.pop().ok_or(StackUnderflow)?)
 	}
@@ -100,68 +123,77 @@ impl ForthInterpreter {
 		&self.constants
 	}
 
-	pub fn get_native_words_dump(&self) -> &HashMap<String, WordFn> {
-		&self.native_words
+	pub fn get_native_words_dump(&self) -> &HashMap<String, usize> {
+		&self.native_names
 	}
 
-	pub fn get_user_words_dump(&self) -> &HashMap<String, WordElement> {
+	pub fn get_user_words_dump(&self) -> &Vec<WordElement> {
 		&self.user_words
 	}
 
-	pub fn bool(literal: &Literal) -> bool {
+	/// The interpreter's truthiness convention: only the `-1` produced by
+	/// comparison words (see [`crate::ternary`]) and strings (empty ones
+	/// included) count as true, so e.g. `2 IF ... THEN` takes the false branch. Any other
+	/// operand (a float, pointer, array, or `Unknown`) can't sensibly stand
+	/// in for a flag, so this errors instead of guessing.
+	pub fn bool(literal: &Literal) -> Result<bool> {
 		match &literal {
 			&Literal::Integer(i) => {
-				!(*i != -1i64)
+				Ok(!(*i != -1i64))
 			},
 			Literal::String(_) => {
-				true
+				Ok(true)
 			},
-			_ => unreachable!()
+			_ => Err(InvalidOperands)
 		}
 	}
 
 	fn add(&mut self) -> Result<()> {
 		let (a, b) = self.get_binary_operands()?;
-		if let Literal::Integer(a) = a {
-			if let Literal::Integer(b) = b {
-				self.push(Literal::Integer(a + b));
-				return Ok(())
-			}
+		match (a, b) {
+			(Literal::Integer(a), Literal::Integer(b)) => self.push(Literal::Integer(a + b)),
+			(Literal::Integer(a), Literal::Float(b)) => self.push(Literal::Float(a as f64 + b)),
+			(Literal::Float(a), Literal::Integer(b)) => self.push(Literal::Float(a + b as f64)),
+			(Literal::Float(a), Literal::Float(b)) => self.push(Literal::Float(a + b)),
+			_ => return Err(InvalidOperands),
 		}
-		Err(InvalidOperands)
+		Ok(())
 	}
 
 	fn sub(&mut self) -> Result<()> {
 		let (a, b) = self.get_binary_operands()?;
-		if let Literal::Integer(a) = a {
-			if let Literal::Integer(b) = b {
-				self.push(Literal::Integer(a - b));
-				return Ok(())
-			}
+		match (a, b) {
+			(Literal::Integer(a), Literal::Integer(b)) => self.push(Literal::Integer(a - b)),
+			(Literal::Integer(a), Literal::Float(b)) => self.push(Literal::Float(a as f64 - b)),
+			(Literal::Float(a), Literal::Integer(b)) => self.push(Literal::Float(a - b as f64)),
+			(Literal::Float(a), Literal::Float(b)) => self.push(Literal::Float(a - b)),
+			_ => return Err(InvalidOperands),
 		}
-		Err(InvalidOperands)
+		Ok(())
 	}
 
     fn mul(&mut self) -> Result<()> {
         let (a, b) = self.get_binary_operands()?;
-        if let Literal::Integer(a) = a {
-            if let Literal::Integer(b) = b {
-                self.push(Literal::Integer(a * b));
-                return Ok(()) 
-            }
-        }
-        Err(InvalidOperands)
+		match (a, b) {
+			(Literal::Integer(a), Literal::Integer(b)) => self.push(Literal::Integer(a * b)),
+			(Literal::Integer(a), Literal::Float(b)) => self.push(Literal::Float(a as f64 * b)),
+			(Literal::Float(a), Literal::Integer(b)) => self.push(Literal::Float(a * b as f64)),
+			(Literal::Float(a), Literal::Float(b)) => self.push(Literal::Float(a * b)),
+			_ => return Err(InvalidOperands),
+		}
+		Ok(())
     }
 
 	fn div(&mut self) -> Result<()> {
         let (a, b) = self.get_binary_operands()?;
-        if let Literal::Integer(a) = a {
-            if let Literal::Integer(b) = b {
-                self.push(Literal::Integer(a / b));
-                return Ok(()) 
-            }
-    	}
-        Err(InvalidOperands)
+		match (a, b) {
+			(Literal::Integer(a), Literal::Integer(b)) => self.push(Literal::Integer(a / b)),
+			(Literal::Integer(a), Literal::Float(b)) => self.push(Literal::Float(a as f64 / b)),
+			(Literal::Float(a), Literal::Integer(b)) => self.push(Literal::Float(a / b as f64)),
+			(Literal::Float(a), Literal::Float(b)) => self.push(Literal::Float(a / b)),
+			_ => return Err(InvalidOperands),
+		}
+		Ok(())
     }
 
 	fn dup(&mut self) -> Result<()> {
@@ -307,11 +339,16 @@ impl ForthInterpreter {
 	}
 
 	pub fn execute_line(&mut self, line: &str) -> Result<()> {
-		let line_pair = ForthParser::parse(Rule::line, line).unwrap();
-		let mut line = entities::Line::parse(line_pair.peek().unwrap());
+		let mut line_pairs = ForthParser::parse(Rule::line, line).map_err(|_| UnbalancedControlFlow)?;
+		let line = entities::Line::parse(line_pairs.next().unwrap());
 
-		line.execute(self)?;
+		let ops = self.compile_tokens(&line.into_tokens())?;
+		self.execute_ops(&ops)
+	}
 
+	fn loop_index(&mut self) -> Result<()> {
+		let (index, _limit) = *self.loop_stack.last().ok_or(StackUnderflow)?;
+		self.push(Literal::Integer(index));
 		Ok(())
 	}
 
@@ -340,17 +377,85 @@ mod tests {
 	}
 
 	#[test]
-	fn test_variable() {
-		let mut interpreter = ForthInterpreter::new();
-    
-		interpreter.execute_line("variable user_var").unwrap();
-		interpreter.execute_line("123 user_var !").unwrap();
-
-		let value = interpreter.get_last_literal().unwrap();
-		if let Literal::Integer(i) = value {
-			println!("{:?}", unsafe { (*i as *const Option<Literal>).as_ref() });
-		}
+	fn test_colon_definition() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line(": sq dup * ;").unwrap();
+		forth.execute_line("6 sq").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(36));
+	}
 
+	#[test]
+	fn test_two_swap() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("9 1 2 3 4 2swap").unwrap();
+		let stack: Vec<Literal> = forth.get_stack_dump().iter().cloned().collect();
+		assert_eq!(stack, vec![
+			Literal::Integer(9), Literal::Integer(3), Literal::Integer(4),
+			Literal::Integer(1), Literal::Integer(2),
+		]);
+	}
+
+	#[test]
+	fn test_map() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("1 2 3 3 >array \"1+\" map").unwrap();
+		assert_eq!(
+			*forth.get_last_literal().unwrap(),
+			Literal::Array(vec![Literal::Integer(2), Literal::Integer(3), Literal::Integer(4)]),
+		);
+	}
+
+	#[test]
+	fn test_if_then() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("1 1 = IF 42 THEN").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(42));
+
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("1 0 = IF 42 THEN 7").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(7));
+	}
+
+	#[test]
+	fn test_if_else_then() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("1 1 = IF 1 ELSE 2 THEN").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(1));
+
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("1 0 = IF 1 ELSE 2 THEN").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(2));
+	}
+
+	#[test]
+	fn test_begin_until() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("0 BEGIN 1+ dup 5 = UNTIL").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(5));
+	}
+
+	#[test]
+	fn test_begin_while_repeat() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line(": count 0 BEGIN dup 5 < WHILE 1+ REPEAT ; count").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(5));
+	}
+
+	#[test]
+	fn test_do_loop() {
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("0 5 0 DO 1+ LOOP").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(5));
+	}
+
+	#[test]
+	fn test_nested_if_inside_do_loop() {
+		// Sums the even loop indices from 0 to 5 (0 + 2 + 4 = 6), exercising an
+		// IF/THEN compiled inside a DO/LOOP body: this is the shape that
+		// exposed the jump-target relocation bug in the control-flow compiler.
+		let mut forth = ForthInterpreter::new();
+		forth.execute_line("0 6 0 DO i 2 mod 0= IF i + THEN LOOP").unwrap();
+		assert_eq!(*forth.get_last_literal().unwrap(), Literal::Integer(6));
 	}
 
 }
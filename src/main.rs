@@ -0,0 +1,96 @@
+//! Interactive front end for [`forth_interpreter::ForthInterpreter`]: a
+//! rustyline-backed REPL with persistent history, multi-line buffering for
+//! colon definitions and control structures, and a handful of dot-commands
+//! for inspecting live interpreter state.
+
+use forth_interpreter::ForthInterpreter;
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+
+const HISTORY_FILE: &str = ".forth_history";
+
+/// Net change in nesting depth contributed by words that open or close a
+/// colon definition or control structure, so the REPL knows when to keep
+/// buffering instead of executing.
+fn pending_depth(buffer: &str) -> i32 {
+	buffer.split_whitespace().fold(0, |depth, token| match token {
+		":" | "IF" | "BEGIN" | "DO" => depth + 1,
+		";" | "THEN" | "UNTIL" | "REPEAT" | "LOOP" => depth - 1,
+		_ => depth,
+	})
+}
+
+fn print_words(interpreter: &ForthInterpreter) {
+	let mut names: Vec<&String> = interpreter.get_native_words_dump().keys().collect();
+	names.extend(interpreter.get_user_words_dump().iter().map(|word| &word.name));
+	names.sort();
+	for name in names {
+		print!("{} ", name);
+	}
+	println!();
+}
+
+fn print_vars(interpreter: &ForthInterpreter) {
+	for variable in interpreter.get_vars_dump() {
+		println!("{}", variable);
+	}
+}
+
+fn main() {
+	let mut interpreter = ForthInterpreter::new();
+	let mut editor = Editor::<()>::new();
+	let _ = editor.load_history(HISTORY_FILE);
+
+	let mut buffer = String::new();
+	loop {
+		let prompt = if buffer.is_empty() { "forth> " } else { "  ...> " };
+		match editor.readline(prompt) {
+			Ok(line) => {
+				editor.add_history_entry(line.as_str());
+
+				if buffer.is_empty() {
+					match line.trim() {
+						".s" => {
+							println!("{}", interpreter.get_stack_dump());
+							continue;
+						}
+						".words" => {
+							print_words(&interpreter);
+							continue;
+						}
+						".vars" => {
+							print_vars(&interpreter);
+							continue;
+						}
+						_ => {}
+					}
+				}
+
+				if !buffer.is_empty() {
+					buffer.push(' ');
+				}
+				buffer.push_str(&line);
+
+				if pending_depth(&buffer) > 0 {
+					continue;
+				}
+
+				match interpreter.execute_line(&buffer) {
+					Ok(()) => match interpreter.get_last_literal() {
+						Ok(top) => println!("{} ok", top),
+						Err(_) => println!("ok"),
+					},
+					Err(err) => println!("{}", err),
+				}
+				buffer.clear();
+			}
+			Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+			Err(err) => {
+				println!("readline error: {}", err);
+				break;
+			}
+		}
+	}
+
+	let _ = editor.save_history(HISTORY_FILE);
+}
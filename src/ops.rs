@@ -0,0 +1,203 @@
+use crate::entities::complex::definition::WordElement;
+use crate::entities::simple::literal::Literal;
+use crate::entities::Token;
+use crate::errors::ForthError::{InvalidOperands, StackUnderflow, UnknownWord};
+use crate::ForthInterpreter;
+use crate::Result;
+
+/// A single resolved instruction produced by [`ForthInterpreter::compile_tokens`].
+///
+/// Name lookups and control-flow nesting are resolved once, here, so the
+/// executor never re-parses a line or re-hashes a word name.
+#[derive(Debug, Clone)]
+pub enum Op {
+	PushLiteral(Literal),
+	CallNative(usize),
+	CallUser(usize),
+	/// Pop the top of the stack; jump to the target if it is falsy.
+	BranchFalse(usize),
+	Jump(usize),
+	/// Pop limit/index; jump past the loop if it wouldn't run, else push the `DO` frame.
+	DoSetup(usize),
+	/// Advance the `DO` frame; jump back to the loop body if it should run again.
+	DoNext(usize),
+}
+
+impl ForthInterpreter {
+	pub(crate) fn compile_tokens(&mut self, tokens: &[Token]) -> Result<Vec<Op>> {
+		let mut ops = Vec::new();
+		self.compile_tokens_into(tokens, &mut ops)?;
+		Ok(ops)
+	}
+
+	/// Like [`Self::compile_tokens`], but appends directly to an existing
+	/// instruction stream instead of returning a fresh one. Branches must
+	/// compile here rather than into their own `Vec` and get spliced in,
+	/// since a branch's `BranchFalse`/`Jump`/`DoSetup`/`DoNext` targets are
+	/// absolute positions computed from `ops.len()` — splicing a
+	/// separately-compiled sub-vector would leave those targets relative to
+	/// the wrong base.
+	fn compile_tokens_into(&mut self, tokens: &[Token], ops: &mut Vec<Op>) -> Result<()> {
+		for token in tokens {
+			self.compile_token(token, ops)?;
+		}
+		Ok(())
+	}
+
+	fn compile_token(&mut self, token: &Token, ops: &mut Vec<Op>) -> Result<()> {
+		match token {
+			Token::Literal(literal) => ops.push(Op::PushLiteral(literal.clone())),
+			Token::Call(name) => ops.push(self.resolve_call(name)?),
+			Token::Definition { name, body } => {
+				let body = self.compile_tokens(body)?;
+				self.register_word(name.clone(), body);
+			}
+			Token::If { true_branch, false_branch } => self.compile_if(true_branch, false_branch, ops)?,
+			Token::BeginUntil { body } => self.compile_begin_until(body, ops)?,
+			Token::BeginWhileRepeat { condition, body } => self.compile_begin_while_repeat(condition, body, ops)?,
+			Token::DoLoop { body } => self.compile_do_loop(body, ops)?,
+		}
+		Ok(())
+	}
+
+	fn resolve_call(&self, name: &str) -> Result<Op> {
+		if let Some(&index) = self.native_names.get(name) {
+			return Ok(Op::CallNative(index));
+		}
+		if let Some(&index) = self.user_names.get(name) {
+			return Ok(Op::CallUser(index));
+		}
+		Err(UnknownWord(name.to_string()))
+	}
+
+	/// Invoke a word by name right away, rather than compiling a resolved
+	/// [`Op`] for it. Used by words like `map` that only learn which word to
+	/// run at runtime, from a value on the stack.
+	pub(crate) fn call_by_name(&mut self, name: &str) -> Result<()> {
+		if let Some(&index) = self.native_names.get(name) {
+			let native = self.native_words[index];
+			return native(self);
+		}
+		if let Some(&index) = self.user_names.get(name) {
+			let body = self.user_words[index].body.clone();
+			return self.execute_ops(&body);
+		}
+		Err(UnknownWord(name.to_string()))
+	}
+
+	fn register_word(&mut self, name: String, body: Vec<Op>) {
+		if let Some(&index) = self.user_names.get(&name) {
+			self.user_words[index] = WordElement { name, body };
+		} else {
+			let index = self.user_words.len();
+			self.user_names.insert(name.clone(), index);
+			self.user_words.push(WordElement { name, body });
+		}
+	}
+
+	fn compile_if(&mut self, true_branch: &[Token], false_branch: &[Token], ops: &mut Vec<Op>) -> Result<()> {
+		let branch_false_pos = ops.len();
+		ops.push(Op::BranchFalse(0));
+		self.compile_tokens_into(true_branch, ops)?;
+
+		if false_branch.is_empty() {
+			let end = ops.len();
+			ops[branch_false_pos] = Op::BranchFalse(end);
+		} else {
+			let jump_pos = ops.len();
+			ops.push(Op::Jump(0));
+			let else_start = ops.len();
+			ops[branch_false_pos] = Op::BranchFalse(else_start);
+			self.compile_tokens_into(false_branch, ops)?;
+			let end = ops.len();
+			ops[jump_pos] = Op::Jump(end);
+		}
+		Ok(())
+	}
+
+	fn compile_begin_until(&mut self, body: &[Token], ops: &mut Vec<Op>) -> Result<()> {
+		let start = ops.len();
+		self.compile_tokens_into(body, ops)?;
+		ops.push(Op::BranchFalse(start));
+		Ok(())
+	}
+
+	fn compile_begin_while_repeat(&mut self, condition: &[Token], body: &[Token], ops: &mut Vec<Op>) -> Result<()> {
+		let start = ops.len();
+		self.compile_tokens_into(condition, ops)?;
+
+		let branch_pos = ops.len();
+		ops.push(Op::BranchFalse(0));
+
+		self.compile_tokens_into(body, ops)?;
+		ops.push(Op::Jump(start));
+
+		let end = ops.len();
+		ops[branch_pos] = Op::BranchFalse(end);
+		Ok(())
+	}
+
+	fn compile_do_loop(&mut self, body: &[Token], ops: &mut Vec<Op>) -> Result<()> {
+		let setup_pos = ops.len();
+		ops.push(Op::DoSetup(0));
+
+		let body_start = ops.len();
+		self.compile_tokens_into(body, ops)?;
+		ops.push(Op::DoNext(body_start));
+
+		let end = ops.len();
+		ops[setup_pos] = Op::DoSetup(end);
+		Ok(())
+	}
+
+	pub(crate) fn execute_ops(&mut self, ops: &[Op]) -> Result<()> {
+		let mut pc = 0usize;
+		while pc < ops.len() {
+			match &ops[pc] {
+				Op::PushLiteral(literal) => {
+					self.push(literal.clone());
+					pc += 1;
+				}
+				Op::CallNative(index) => {
+					let native = self.native_words[*index];
+					native(self)?;
+					pc += 1;
+				}
+				Op::CallUser(index) => {
+					let body = self.user_words[*index].body.clone();
+					self.execute_ops(&body)?;
+					pc += 1;
+				}
+				Op::BranchFalse(target) => {
+					let flag = self.stack.pop().ok_or(StackUnderflow)?;
+					pc = if ForthInterpreter::bool(&flag)? { pc + 1 } else { *target };
+				}
+				Op::Jump(target) => pc = *target,
+				Op::DoSetup(end) => {
+					let (limit, index) = self.get_binary_operands()?;
+					if let (Literal::Integer(limit), Literal::Integer(index)) = (limit, index) {
+						if index >= limit {
+							pc = *end;
+						} else {
+							self.loop_stack.push((index, limit));
+							pc += 1;
+						}
+					} else {
+						return Err(InvalidOperands);
+					}
+				}
+				Op::DoNext(start) => {
+					let (index, limit) = self.loop_stack.pop().expect("DoNext without a matching DoSetup");
+					let next = index + 1;
+					if next < limit {
+						self.loop_stack.push((next, limit));
+						pc = *start;
+					} else {
+						pc += 1;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,10 @@
+use pest::iterators::Pair;
+use pest_derive::Parser;
+
+#[derive(Parser)]
+#[grammar = "parser/forth.pest"]
+pub struct ForthParser;
+
+pub trait Parse {
+	fn parse(pair: Pair<Rule>) -> Self;
+}
@@ -0,0 +1,56 @@
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone)]
+pub struct Stack<T> {
+	items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+	pub fn new() -> Self {
+		Self { items: Vec::new() }
+	}
+
+	pub fn push(&mut self, value: T) {
+		self.items.push(value);
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		self.items.pop()
+	}
+
+	pub fn last(&self) -> Option<&T> {
+		self.items.last()
+	}
+
+	pub fn length(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn get(&self, index: usize) -> &T {
+		&self.items[index]
+	}
+
+	pub fn remove(&mut self, index: usize) -> T {
+		self.items.remove(index)
+	}
+
+	pub fn insert(&mut self, index: usize, value: T) {
+		self.items.insert(index, value);
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<T> {
+		self.items.iter()
+	}
+}
+
+impl<T: Display> Display for Stack<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (i, item) in self.items.iter().enumerate() {
+			if i > 0 {
+				write!(f, " ")?;
+			}
+			write!(f, "{}", item)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,3 @@
+//! Registration helpers for words that are wired into a fresh [`crate::ForthInterpreter`].
+
+pub mod stdlib;
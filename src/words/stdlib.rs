@@ -0,0 +1,400 @@
+//! A broader set of native words layered on top of the handful wired up in
+//! [`crate::ForthInterpreter::new`]: extra arithmetic, stack shuffling, and
+//! comparison helpers. The table is plain data so callers can inspect (or, in
+//! principle, extend) it the same way they inspect the primitive words via
+//! [`crate::ForthInterpreter::get_native_words_dump`].
+
+use crate::entities::simple::literal::Literal;
+use crate::errors::ForthError::{InvalidOperands, StackUnderflow};
+use crate::ternary;
+use crate::{ForthInterpreter, Result, WordFn};
+
+pub(crate) fn table() -> Vec<(&'static str, WordFn)> {
+	vec![
+		("mod", ForthInterpreter::modulo), ("/mod", ForthInterpreter::div_mod),
+		("abs", ForthInterpreter::abs), ("negate", ForthInterpreter::negate),
+		("min", ForthInterpreter::min), ("max", ForthInterpreter::max),
+		("1+", ForthInterpreter::one_plus), ("1-", ForthInterpreter::one_minus),
+		("2*", ForthInterpreter::two_mul), ("2/", ForthInterpreter::two_div),
+
+		("nip", ForthInterpreter::nip), ("tuck", ForthInterpreter::tuck),
+		("2dup", ForthInterpreter::two_dup), ("2drop", ForthInterpreter::two_drop),
+		("2swap", ForthInterpreter::two_swap), ("-rot", ForthInterpreter::minus_rot),
+		("depth", ForthInterpreter::depth), ("pick", ForthInterpreter::pick),
+		("roll", ForthInterpreter::roll),
+
+		("<>", ForthInterpreter::not_equal), ("<=", ForthInterpreter::less_equal),
+		(">=", ForthInterpreter::greater_equal), ("0=", ForthInterpreter::zero_equal),
+		("0<", ForthInterpreter::zero_less),
+
+		("f+", ForthInterpreter::f_add), ("f-", ForthInterpreter::f_sub),
+		("f*", ForthInterpreter::f_mul), ("f/", ForthInterpreter::f_div),
+		("fsqrt", ForthInterpreter::f_sqrt),
+		("i>f", ForthInterpreter::i_to_f), ("f>i", ForthInterpreter::f_to_i),
+
+		(">array", ForthInterpreter::collect_array), ("array@", ForthInterpreter::array_fetch),
+		("array!", ForthInterpreter::array_store), ("length", ForthInterpreter::array_length),
+		("append", ForthInterpreter::array_append), ("map", ForthInterpreter::array_map),
+	]
+}
+
+impl ForthInterpreter {
+	fn modulo(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let Literal::Integer(a) = a {
+			if let Literal::Integer(b) = b {
+				self.push(Literal::Integer(a % b));
+				return Ok(())
+			}
+		}
+		Err(InvalidOperands)
+	}
+
+	fn div_mod(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let Literal::Integer(a) = a {
+			if let Literal::Integer(b) = b {
+				self.push(Literal::Integer(a % b));
+				self.push(Literal::Integer(a / b));
+				return Ok(())
+			}
+		}
+		Err(InvalidOperands)
+	}
+
+	fn abs(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(a.abs()));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn negate(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(-a));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn min(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let Literal::Integer(a) = a {
+			if let Literal::Integer(b) = b {
+				self.push(Literal::Integer(a.min(b)));
+				return Ok(())
+			}
+		}
+		Err(InvalidOperands)
+	}
+
+	fn max(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let Literal::Integer(a) = a {
+			if let Literal::Integer(b) = b {
+				self.push(Literal::Integer(a.max(b)));
+				return Ok(())
+			}
+		}
+		Err(InvalidOperands)
+	}
+
+	fn one_plus(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(a + 1));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn one_minus(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(a - 1));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn two_mul(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(a * 2));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn two_div(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Integer(a / 2));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn nip(&mut self) -> Result<()> {
+		let (_a, b) = self.get_binary_operands()?;
+		self.push(b);
+		Ok(())
+	}
+
+	fn tuck(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		self.push(b.clone());
+		self.push(a);
+		self.push(b);
+		Ok(())
+	}
+
+	fn two_dup(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		self.push(a.clone());
+		self.push(b.clone());
+		self.push(a);
+		self.push(b);
+		Ok(())
+	}
+
+	fn two_drop(&mut self) -> Result<()> {
+		self.get_binary_operands()?;
+		Ok(())
+	}
+
+	fn two_swap(&mut self) -> Result<()> {
+		let length = self.stack.length();
+		if length >= 4 {
+			let d = self.stack.remove(length - 1);
+			let c = self.stack.remove(length - 2);
+			self.stack.insert(length - 4, c);
+			self.stack.insert(length - 3, d);
+			return Ok(())
+		}
+		Err(StackUnderflow)
+	}
+
+	fn minus_rot(&mut self) -> Result<()> {
+		let length = self.stack.length();
+		if length >= 3 {
+			let top = self.stack.remove(length - 1);
+			self.stack.insert(length - 3, top);
+			return Ok(())
+		}
+		Err(StackUnderflow)
+	}
+
+	fn depth(&mut self) -> Result<()> {
+		self.push(Literal::Integer(self.stack.length() as i64));
+		Ok(())
+	}
+
+	fn pick(&mut self) -> Result<()> {
+		let n = self.get_unary_operand()?;
+		if let Literal::Integer(n) = n {
+			let length = self.stack.length();
+			let index = length.checked_sub(1 + n as usize).ok_or(StackUnderflow)?;
+			self.push(self.stack.get(index).clone());
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn roll(&mut self) -> Result<()> {
+		let n = self.get_unary_operand()?;
+		if let Literal::Integer(n) = n {
+			let length = self.stack.length();
+			let index = length.checked_sub(1 + n as usize).ok_or(StackUnderflow)?;
+			let rolled = self.stack.remove(index);
+			self.stack.push(rolled);
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn not_equal(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		self.push(Literal::Integer(ternary!(a != b, -1, 0)));
+		Ok(())
+	}
+
+	fn less_equal(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		self.push(Literal::Integer(ternary!(a <= b, -1, 0)));
+		Ok(())
+	}
+
+	fn greater_equal(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		self.push(Literal::Integer(ternary!(a >= b, -1, 0)));
+		Ok(())
+	}
+
+	fn zero_equal(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		self.push(Literal::Integer(ternary!(a == 0i64.into(), -1, 0)));
+		Ok(())
+	}
+
+	fn zero_less(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		self.push(Literal::Integer(ternary!(a < 0i64.into(), -1, 0)));
+		Ok(())
+	}
+
+	fn f_add(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let (Literal::Float(a), Literal::Float(b)) = (a, b) {
+			self.push(Literal::Float(a + b));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn f_sub(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let (Literal::Float(a), Literal::Float(b)) = (a, b) {
+			self.push(Literal::Float(a - b));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn f_mul(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let (Literal::Float(a), Literal::Float(b)) = (a, b) {
+			self.push(Literal::Float(a * b));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn f_div(&mut self) -> Result<()> {
+		let (a, b) = self.get_binary_operands()?;
+		if let (Literal::Float(a), Literal::Float(b)) = (a, b) {
+			self.push(Literal::Float(a / b));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn f_sqrt(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Float(a) = a {
+			self.push(Literal::Float(a.sqrt()));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn i_to_f(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Integer(a) = a {
+			self.push(Literal::Float(a as f64));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn f_to_i(&mut self) -> Result<()> {
+		let a = self.get_unary_operand()?;
+		if let Literal::Float(a) = a {
+			self.push(Literal::Integer(a as i64));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn collect_array(&mut self) -> Result<()> {
+		let n = self.get_unary_operand()?;
+		if let Literal::Integer(n) = n {
+			if n < 0 {
+				return Err(InvalidOperands);
+			}
+			let n = n as usize;
+			let mut items = Vec::with_capacity(n);
+			for _ in 0..n {
+				items.push(self.stack.pop().ok_or(StackUnderflow)?);
+			}
+			items.reverse();
+			self.push(Literal::Array(items));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn array_fetch(&mut self) -> Result<()> {
+		let (array, index) = self.get_binary_operands()?;
+		if let (Literal::Array(items), Literal::Integer(index)) = (array, index) {
+			let item = items.get(index as usize).ok_or(InvalidOperands)?;
+			self.push(item.clone());
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn array_store(&mut self) -> Result<()> {
+		let elem = self.get_unary_operand()?;
+		let (array, index) = self.get_binary_operands()?;
+		if let (Literal::Array(mut items), Literal::Integer(index)) = (array, index) {
+			let index = index as usize;
+			if index >= items.len() {
+				return Err(InvalidOperands);
+			}
+			items[index] = elem;
+			self.push(Literal::Array(items));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn array_length(&mut self) -> Result<()> {
+		let array = self.get_unary_operand()?;
+		if let Literal::Array(items) = array {
+			self.push(Literal::Integer(items.len() as i64));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn array_append(&mut self) -> Result<()> {
+		let (array, elem) = self.get_binary_operands()?;
+		if let Literal::Array(mut items) = array {
+			items.push(elem);
+			self.push(Literal::Array(items));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+
+	fn array_map(&mut self) -> Result<()> {
+		let (array, name) = self.get_binary_operands()?;
+		if let (Literal::Array(items), Literal::String(name)) = (array, name) {
+			let mut results = Vec::with_capacity(items.len());
+			for item in items {
+				let before = self.stack.length();
+				self.push(item);
+				if let Err(err) = self.call_by_name(&name) {
+					while self.stack.length() > before {
+						self.stack.pop();
+					}
+					return Err(err);
+				}
+				if self.stack.length() != before + 1 {
+					while self.stack.length() > before {
+						self.stack.pop();
+					}
+					return Err(InvalidOperands);
+				}
+				results.push(self.stack.pop().ok_or(StackUnderflow)?);
+			}
+			self.push(Literal::Array(results));
+			return Ok(())
+		}
+		Err(InvalidOperands)
+	}
+}